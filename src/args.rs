@@ -0,0 +1,73 @@
+use std::convert::Infallible;
+
+use anyhow::Result;
+use pico_args::Arguments;
+
+/**
+ * Command line arguments.
+ *
+ * The filters (`only`/`exclude`) are matched against probe names
+ * case-insensitively; everything else toggles rendering behavior.
+ */
+#[derive(Debug, Default)]
+pub struct Args {
+    /// Emit the gathered facts as JSON instead of the colorized layout.
+    pub json: bool,
+
+    /// Suppress the ASCII logo.
+    pub no_logo: bool,
+
+    /// Never emit color, overriding the TTY check.
+    pub no_color: bool,
+
+    /// On a probe failure, print the failing command and its stderr.
+    pub verbose: bool,
+
+    /// Gather facts from a remote host (`user@server`) over SSH.
+    pub host: Option<String>,
+
+    /// If set, only these probes run (whitelist).
+    pub only: Option<Vec<String>>,
+
+    /// These probes are skipped (blacklist).
+    pub exclude: Vec<String>,
+}
+
+/**
+ * Parse the process arguments into an [`Args`].
+ */
+pub fn parse() -> Result<Args> {
+    let mut pargs = Arguments::from_env();
+
+    let args = Args {
+        json: pargs.contains("--json"),
+        no_logo: pargs.contains("--no-logo"),
+        no_color: pargs.contains("--no-color"),
+        verbose: pargs.contains(["-v", "--verbose"]),
+        host: pargs.opt_value_from_str("--host")?,
+        only: pargs.opt_value_from_fn("--only", parse_list)?,
+        exclude: pargs
+            .opt_value_from_fn("--exclude", parse_list)?
+            .unwrap_or_default(),
+    };
+
+    Ok(args)
+}
+
+/// Split a comma-separated `--only`/`--exclude` value into trimmed names.
+fn parse_list(s: &str) -> std::result::Result<Vec<String>, Infallible> {
+    Ok(s.split(',').map(|name| name.trim().to_string()).collect())
+}
+
+impl Args {
+    /// Whether `name` should be gathered given the `--only`/`--exclude`
+    /// filters.
+    pub fn includes(&self, name: &str) -> bool {
+        if let Some(only) = &self.only {
+            if !only.iter().any(|n| n.eq_ignore_ascii_case(name)) {
+                return false;
+            }
+        }
+        !self.exclude.iter().any(|n| n.eq_ignore_ascii_case(name))
+    }
+}
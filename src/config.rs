@@ -0,0 +1,121 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/**
+ * A single field to gather and render, in the order it appears in the config.
+ */
+#[derive(Debug, Deserialize)]
+pub struct Module {
+    /// The probe name (e.g. "OS", "CPU") - selects which `get_*` runs.
+    pub name: String,
+
+    /// The label shown to the left of the value; defaults to `name`.
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+impl Module {
+    /// The text to display for this module, falling back to its name.
+    pub fn label(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/**
+ * The three color escape strings that `util::colorize` substitutes.
+ *
+ * - `c0` -> reset color/formatting
+ * - `c1` -> the primary (orange) color
+ * - `c2` -> the secondary (dim gray) color
+ */
+#[derive(Debug, Deserialize)]
+pub struct Colors {
+    pub c0: String,
+    pub c1: String,
+    pub c2: String,
+}
+
+impl Default for Colors {
+    fn default() -> Self {
+        Colors {
+            c0: "\x1B[0m".into(),
+            c1: "\x1B[0m\x1B[38;5;208m".into(),
+            c2: "\x1B[0m\x1B[38;5;8m".into(),
+        }
+    }
+}
+
+/**
+ * User configuration, parsed from `~/.config/omnifetch/config.toml` when
+ * present. Every field falls back to today's hardcoded behavior so the
+ * file is entirely optional.
+ */
+#[derive(Debug, Deserialize)]
+pub struct Config {
+    #[serde(default = "default_modules")]
+    pub modules: Vec<Module>,
+
+    #[serde(default)]
+    pub colors: Colors,
+
+    /// Optional path to a custom ASCII logo, used instead of the embedded one.
+    #[serde(default)]
+    pub logo: Option<PathBuf>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            modules: default_modules(),
+            colors: Colors::default(),
+            logo: None,
+        }
+    }
+}
+
+/// The built-in module list, matching the order `main()` used before configs
+/// existed.
+fn default_modules() -> Vec<Module> {
+    [
+        "OS",
+        "Kernel",
+        "Zonename",
+        "Boot Env",
+        "CPU",
+        "Uptime",
+        "Memory",
+        "SMF",
+        "Zones",
+        "ZFS",
+    ]
+    .into_iter()
+    .map(|name| Module { name: name.into(), label: None })
+    .collect()
+}
+
+/**
+ * Load the config from `~/.config/omnifetch/config.toml`, or fall back to the
+ * default (today's behavior) when the file is absent.
+ */
+pub fn load() -> Result<Config> {
+    let path = match config_path() {
+        Some(path) if path.exists() => path,
+        _ => return Ok(Config::default()),
+    };
+
+    let data = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let config = toml::from_str(&data)
+        .with_context(|| format!("failed to parse {}", path.display()))?;
+
+    Ok(config)
+}
+
+fn config_path() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .map(|home| PathBuf::from(home).join(".config/omnifetch/config.toml"))
+}
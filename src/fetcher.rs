@@ -0,0 +1,100 @@
+use anyhow::{Context, Result};
+
+use crate::run;
+use crate::transport::{self, Transport};
+use crate::util::human_bytes;
+
+/**
+ * Something that can gather a single fact under a name.
+ *
+ * The default implementation ([`Command`]) shells out through `util::run`,
+ * but the trait lets a probe be backed by a Rust crate instead of a
+ * subprocess - faster, dependency-light, and the seam for eventually reading
+ * the same facts on other platforms.
+ */
+pub trait Fetcher {
+    /// The module name this fetcher answers to (e.g. "OS", "CPU").
+    fn name(&self) -> &'static str;
+
+    /// Gather the fact, or an error describing why the probe failed.
+    fn fetch(&self) -> Result<String>;
+}
+
+/**
+ * A fetcher backed by one or more external commands, wrapping the `get_*`
+ * gatherers that drive illumos tools through the `run!` macro.
+ */
+pub struct Command {
+    name: &'static str,
+    probe: fn() -> Result<String>,
+}
+
+impl Command {
+    pub fn new(name: &'static str, probe: fn() -> Result<String>) -> Self {
+        Command { name, probe }
+    }
+}
+
+impl Fetcher for Command {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn fetch(&self) -> Result<String> {
+        (self.probe)()
+    }
+}
+
+/**
+ * A native, non-subprocess fetcher for the zone name, reading it from the
+ * `zonename` crate directly instead of execing `zonename(1)`.
+ *
+ * Native reads only describe the *local* box, so under the ssh transport the
+ * probe falls back to running `zonename(1)` on the remote host.
+ */
+pub struct Zonename;
+
+impl Fetcher for Zonename {
+    fn name(&self) -> &'static str {
+        "Zonename"
+    }
+
+    fn fetch(&self) -> Result<String> {
+        match transport::current() {
+            Transport::Local => Ok(zonename::getzonename()?),
+            Transport::Ssh { .. } => run! { "zonename" },
+        }
+    }
+}
+
+/**
+ * A native, non-subprocess fetcher for total physical memory, the
+ * sysinfo-style `System` read the `Fetcher` trait was designed around. Local
+ * reads come from `sysconf` (no fork); remote hosts fall back to `lgrpinfo`.
+ */
+pub struct Memory;
+
+impl Fetcher for Memory {
+    fn name(&self) -> &'static str {
+        "Memory"
+    }
+
+    fn fetch(&self) -> Result<String> {
+        match transport::current() {
+            Transport::Local => {
+                use nix::unistd::{sysconf, SysconfVar};
+                let pages = sysconf(SysconfVar::_SC_PHYS_PAGES)?
+                    .context("sysconf(_SC_PHYS_PAGES) unavailable")?;
+                let page_size = sysconf(SysconfVar::_SC_PAGESIZE)?
+                    .context("sysconf(_SC_PAGESIZE) unavailable")?;
+                Ok(human_bytes(pages as u64 * page_size as u64))
+            }
+            Transport::Ssh { .. } => {
+                let output = run! { "lgrpinfo -m" }?;
+                let lines: Vec<_> = output.lines().collect();
+                let spl: Vec<_> = lines[1].split(':').collect();
+                Ok(spl[1].trim().into())
+            }
+        }
+    }
+}
@@ -9,29 +9,52 @@
 use std::collections::HashMap;
 use std::env;
 use std::fs;
+use std::thread;
 use std::time::SystemTime;
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use indexmap::IndexMap;
+use serde_json::{json, Value};
 
+mod args;
+mod config;
+mod fetcher;
+mod transport;
 mod util;
+mod zfs;
+
+use fetcher::{Command, Fetcher, Memory, Zonename};
+use transport::Transport;
 
 const FENIX: &str = include_str!("../files/fenix.txt");
 const OMNIOS: &str = include_str!("../files/omnios.txt");
 
 fn get_hostname() -> Result<String> {
-    let name =
-        nix::unistd::gethostname()?.into_string().expect("invalid hostname");
-    Ok(name)
+    match transport::current() {
+        Transport::Local => {
+            let name = nix::unistd::gethostname()?
+                .into_string()
+                .expect("invalid hostname");
+            Ok(name)
+        }
+        Transport::Ssh { .. } => run! { "hostname" },
+    }
 }
 
 fn get_user() -> Result<String> {
-    let user = env::var("USER").context("failed to get user")?;
-    Ok(user)
+    match transport::current() {
+        Transport::Local => env::var("USER").context("failed to get user"),
+        Transport::Ssh { .. } => run! { "id -un" },
+    }
 }
 
 fn get_os() -> Result<String> {
-    let data = fs::read_to_string("/etc/release")?;
+    // read /etc/release locally, or `cat` it over the transport so --host
+    // reports the remote box's OS rather than the local one
+    let data = match transport::current() {
+        Transport::Local => fs::read_to_string("/etc/release")?,
+        Transport::Ssh { .. } => run! { "cat /etc/release" }?,
+    };
     let line = data.lines().next().context("expected at least 1 line")?;
 
     let s = line.trim().into();
@@ -39,11 +62,6 @@ fn get_os() -> Result<String> {
     Ok(s)
 }
 
-fn get_zonename() -> Result<String> {
-    let name = zonename::getzonename()?;
-    Ok(name)
-}
-
 fn get_kernel() -> Result<String> {
     run! { "uname -v" }
 }
@@ -69,16 +87,6 @@ fn get_cpu() -> Result<String> {
     Ok(s)
 }
 
-fn get_memory() -> Result<String> {
-    let output = run! { "lgrpinfo -m" }?;
-    let lines: Vec<_> = output.lines().collect();
-
-    let spl: Vec<_> = lines[1].split(':').collect();
-    let s = spl[1].trim().into();
-
-    Ok(s)
-}
-
 fn get_uptime() -> Result<String> {
     let output = run! { "kstat -p unix:0:system_misc:boot_time" }?;
     let spl: Vec<_> = output.split('\t').collect();
@@ -151,49 +159,133 @@ fn get_zones() -> Result<String> {
 }
 
 fn get_zpools() -> Result<String> {
-    let output = run! { "zpool list -Ho name,cap,alloc,size" }?;
+    zfs::report()
+}
 
-    let mut zpools = vec![];
-    for line in output.lines() {
-        let spl: Vec<_> = line.split_whitespace().collect();
-        let name = spl[0].to_string();
-        let _used = spl[1].to_string();
-        let alloc = spl[2].to_string();
-        let size = spl[3].to_string();
+/// The registry of named probes, mapping a module name to its [`Fetcher`].
+/// Most are [`Command`]-backed; `Zonename` and `Memory` are gathered natively
+/// for the local host. The config
+/// selects and orders entries out of this map; the arg filters index into it.
+fn registry() -> IndexMap<&'static str, Box<dyn Fetcher + Send + Sync>> {
+    let fetchers: Vec<Box<dyn Fetcher + Send + Sync>> = vec![
+        Box::new(Command::new("OS", get_os)),
+        Box::new(Command::new("Kernel", get_kernel)),
+        Box::new(Zonename),
+        Box::new(Command::new("Boot Env", get_bootenvironment)),
+        Box::new(Command::new("CPU", get_cpu)),
+        Box::new(Command::new("Uptime", get_uptime)),
+        Box::new(Memory),
+        Box::new(Command::new("SMF", get_smf)),
+        Box::new(Command::new("Zones", get_zones)),
+        Box::new(Command::new("ZFS", get_zpools)),
+    ];
+
+    fetchers.into_iter().map(|f| (f.name(), f)).collect()
+}
 
-        zpools.push(format!("{} {}/{}", name, alloc, size));
+/// Turn a probe result into a JSON value, replacing a failure with an
+/// `{ "error": "..." }` object so one bad probe doesn't abort the whole run.
+fn jsonify(result: Result<Value>) -> Value {
+    match result {
+        Ok(v) => v,
+        Err(e) => json!({ "error": e.to_string() }),
     }
+}
 
-    let s = zpools.join(", ");
-
-    Ok(s)
+/// Render a probe value for the human-facing layout - the string itself, or
+/// the compact JSON form of an error object.
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
 }
 
 fn main() -> Result<()> {
-    // gather data
-    let mut data = IndexMap::new();
+    let args = args::parse()?;
+
+    // with --host, run every probe command over ssh; colorization still
+    // happens locally based on the local TTY
+    if let Some(host) = &args.host {
+        transport::set(Transport::Ssh { host: host.clone() });
+    }
+
+    let config = config::load()?;
+    let registry = registry();
+
     let user = get_user()?;
     let hostname = get_hostname()?;
-    data.insert("OS", get_os()?);
-    data.insert("Kernel", get_kernel()?);
-    data.insert("Zonename", get_zonename()?);
-    data.insert("Boot Env", get_bootenvironment()?);
-    data.insert("CPU", get_cpu()?);
-    data.insert("Uptime", get_uptime()?);
-    data.insert("Memory", get_memory()?);
-    data.insert("SMF", get_smf()?);
-    data.insert("Zones", get_zones()?);
-    data.insert("ZFS", get_zpools()?);
+
+    // the modules to gather, in display order, honoring the --only/--exclude
+    // filters
+    let selected: Vec<&config::Module> =
+        config.modules.iter().filter(|m| args.includes(&m.name)).collect();
+
+    // most probes fork at least one external process, so run each on its own
+    // thread and join the results back in order - total latency becomes the
+    // slowest probe rather than the sum. Per-probe errors stay isolated.
+    let results: Vec<Result<Value>> = thread::scope(|scope| {
+        let handles: Vec<_> = selected
+            .iter()
+            .map(|module| {
+                scope.spawn(|| {
+                    // ZFS carries structured data for --json; every other
+                    // probe is a plain string
+                    if args.json && module.name == "ZFS" {
+                        return zfs::report_json();
+                    }
+                    match registry.get(module.name.as_str()) {
+                        Some(probe) => probe.fetch().map(Value::String),
+                        None => bail!("unknown module: {}", module.name),
+                    }
+                })
+            })
+            .collect();
+
+        // a panic inside one probe must stay isolated to that probe, not
+        // abort the whole run - turn it into that probe's error value
+        handles
+            .into_iter()
+            .map(|h| {
+                h.join().unwrap_or_else(|_| Err(anyhow!("probe panicked")))
+            })
+            .collect()
+    });
+
+    // join back into the map in the original display order
+    let mut data: IndexMap<String, Value> = IndexMap::new();
+    for (module, result) in selected.iter().zip(results) {
+        // in verbose mode surface the failing command and its stderr rather
+        // than letting the error disappear into an error value
+        if args.verbose {
+            if let Err(e) = &result {
+                eprintln!("omnifetch: {} failed: {:#}", module.name, e);
+            }
+        }
+
+        data.insert(module.label().to_string(), jsonify(result));
+    }
+
+    // in json mode just emit the map (IndexMap preserves insertion order) and
+    // skip the logo/ANSI rendering entirely
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&data)?);
+        return Ok(());
+    }
+
+    let color = !args.no_color && util::should_colorize();
 
     // format output - "output" here will contain all of the data that goes to
-    // the right of the fenix logo
+    // the right of the logo
     let mut output = vec![];
 
-    // first queue up the omnios logo
-    for line in OMNIOS.lines() {
-        output.push(line.to_string());
+    // first queue up the omnios logo, unless the logo is suppressed
+    if !args.no_logo {
+        for line in OMNIOS.lines() {
+            output.push(line.to_string());
+        }
+        output.push("".to_string());
     }
-    output.push("".to_string());
 
     // next format the user and hostname
     output.push(format!("$(c1){}$(c2)@$(c1){}", user, hostname));
@@ -202,22 +294,39 @@ fn main() -> Result<()> {
     output.push("".to_string());
 
     // finally print the gathered data
-    for (key, value) in data {
-        output.push(format!("$(c1){}:$(c0) {}", key, value));
+    for (key, value) in &data {
+        output.push(format!("$(c1){}:$(c0) {}", key, display_value(value)));
     }
 
-    let fenix_lines: Vec<_> = FENIX.lines().collect();
+    // when the logo is suppressed, just print the gathered data on its own
+    if args.no_logo {
+        println!();
+        for line in &output {
+            println!("{}", util::colorize(line, &config.colors, color));
+        }
+        println!();
+        return Ok(());
+    }
+
+    // use the custom logo from the config if one is set, otherwise the
+    // embedded fenix logo
+    let logo = match &config.logo {
+        Some(path) => fs::read_to_string(path)
+            .with_context(|| format!("failed to read logo {}", path.display()))?,
+        None => FENIX.to_string(),
+    };
+    let logo_lines: Vec<_> = logo.lines().collect();
 
-    // generate output by prefixing the gathered data with the fenix logo
+    // generate output by prefixing the gathered data with the logo
     println!();
-    for (i, fenix_line) in fenix_lines.into_iter().enumerate() {
+    for (i, logo_line) in logo_lines.into_iter().enumerate() {
         let output_line = match output.get(i) {
             Some(s) => s,
             None => "",
         };
 
-        let s = format!("{} {}", fenix_line, output_line);
-        println!("{}", util::colorize(&s));
+        let s = format!("{} {}", logo_line, output_line);
+        println!("{}", util::colorize(&s, &config.colors, color));
     }
     println!();
 
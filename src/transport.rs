@@ -0,0 +1,52 @@
+use std::process::Command;
+use std::sync::OnceLock;
+
+/**
+ * How probe commands are executed.
+ *
+ * The default [`Transport::Local`] runs each command directly with
+ * `Command::new`, exactly as before. [`Transport::Ssh`] wraps each command's
+ * argv in `ssh user@server -- <cmd>` so the same `get_*` probes gather a
+ * remote host's facts unchanged. Colorization still happens locally.
+ */
+pub enum Transport {
+    Local,
+    Ssh { host: String },
+}
+
+static TRANSPORT: OnceLock<Transport> = OnceLock::new();
+
+/**
+ * Install the process-wide transport. Called once at startup before any
+ * probe runs; if never called, [`Transport::Local`] is used.
+ */
+pub fn set(transport: Transport) {
+    let _ = TRANSPORT.set(transport);
+}
+
+/**
+ * The active transport, defaulting to [`Transport::Local`].
+ */
+pub fn current() -> &'static Transport {
+    TRANSPORT.get_or_init(|| Transport::Local)
+}
+
+impl Transport {
+    /**
+     * Build the [`Command`] that runs `args` under this transport.
+     */
+    pub fn command(&self, args: &[&str]) -> Command {
+        match self {
+            Transport::Local => {
+                let mut cmd = Command::new(args[0]);
+                cmd.args(&args[1..]);
+                cmd
+            }
+            Transport::Ssh { host } => {
+                let mut cmd = Command::new("ssh");
+                cmd.arg(host).arg("--").args(args);
+                cmd
+            }
+        }
+    }
+}
@@ -1,7 +1,9 @@
 use std::env;
-use std::process::Command;
 
-use anyhow::{ensure, Result};
+use anyhow::{anyhow, Context, Result};
+
+use crate::config::Colors;
+use crate::transport;
 
 /**
  * Run a command string and return the trimmed stdout.
@@ -26,32 +28,63 @@ macro_rules! run {
  * ```
  */
 pub fn run(args: &[&str]) -> Result<String> {
-    let output = Command::new(args[0]).args(&args[1..]).output()?;
-    ensure!(output.status.success(), "exec failed: {}", args.join(" "));
+    let output = transport::current().command(args).output()?;
+    if !output.status.success() {
+        // keep the command as the outer message so `to_string()` stays
+        // concise, with the stderr as the source that `{:#}` reveals under
+        // `--verbose`
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(anyhow!("{}", stderr))
+            .with_context(|| format!("exec failed: {}", args.join(" ")));
+    }
     let s = String::from_utf8(output.stdout)?.trim().to_string();
     Ok(s)
 }
 
+/**
+ * Format a byte count as a short human-readable string, e.g. `3.1T`.
+ */
+pub fn human_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 6] = ["B", "K", "M", "G", "T", "P"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{}{}", bytes, UNITS[unit])
+    } else {
+        format!("{:.1}{}", size, UNITS[unit])
+    }
+}
+
 /**
  * Replace color codes with the ansi string to colorize the output.
  *
  * - $(c0) -> reset color/formatting
- * - $(c1) -> orange color
- * - $(c2) -> dim gray
+ * - $(c1) -> primary (orange) color
+ * - $(c2) -> secondary (dim gray) color
+ *
+ * The escape strings come from the config so they can be rebranded. When
+ * `color` is false the codes are stripped instead of substituted.
  */
-pub fn colorize(s: &str) -> String {
-    if should_colorize() {
-        s.replace("$(c0)", "\x1B[0m")
-            .replace("$(c1)", "\x1B[0m\x1B[38;5;208m")
-            .replace("$(c2)", "\x1B[0m\x1B[38;5;8m")
+pub fn colorize(s: &str, colors: &Colors, color: bool) -> String {
+    if color {
+        s.replace("$(c0)", &colors.c0)
+            .replace("$(c1)", &colors.c1)
+            .replace("$(c2)", &colors.c2)
     } else {
         s.replace("$(c0)", "").replace("$(c1)", "").replace("$(c2)", "")
     }
 }
 
 /**
- * Check if we should emit color.
+ * Check if we should emit color, based on `NO_COLOR` and whether stdout is a
+ * TTY. Callers can still force color off via `--no-color`.
  */
-fn should_colorize() -> bool {
+pub fn should_colorize() -> bool {
     env::var_os("NO_COLOR").is_none() && nix::unistd::isatty(1).unwrap_or(false)
 }
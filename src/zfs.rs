@@ -0,0 +1,212 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::run;
+use crate::util::human_bytes;
+
+/**
+ * Health and capacity of a single zpool.
+ *
+ * Kept structured (rather than a pre-formatted string) so the same data can
+ * feed the JSON output mode as well as the one-line text summary.
+ */
+#[derive(Debug, Serialize)]
+pub struct Pool {
+    pub name: String,
+    pub health: String,
+    pub capacity: String,
+    pub alloc: String,
+    pub size: String,
+    pub scrub_status: String,
+}
+
+impl Pool {
+    /// The one-line text form, e.g. `tank DEGRADED 12.3T/20T, scrub done`.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} {} {}/{}, {}",
+            self.name, self.health, self.alloc, self.size, self.scrub_status
+        )
+    }
+}
+
+/**
+ * Aggregate snapshot count and space across every dataset.
+ */
+#[derive(Debug, Serialize)]
+pub struct Snapshots {
+    pub count: usize,
+    pub used: u64,
+}
+
+/**
+ * A full ZFS report: every pool plus the snapshot aggregation. Gathered once
+ * and rendered either as the one-line text summary or as structured JSON.
+ */
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub pools: Vec<Pool>,
+    pub snapshots: Snapshots,
+}
+
+impl Report {
+    /// The one-line text form, e.g.
+    /// `tank DEGRADED 12.3T/20T, scrub 3d ago | 412 snapshots, 3.1T`.
+    pub fn summary(&self) -> String {
+        let summaries: Vec<_> = self.pools.iter().map(Pool::summary).collect();
+        format!(
+            "{} | {} snapshots, {}",
+            summaries.join(", "),
+            self.snapshots.count,
+            human_bytes(self.snapshots.used)
+        )
+    }
+}
+
+/// Gather every pool with its health, capacity, and scrub state.
+fn gather_pools() -> Result<Vec<Pool>> {
+    let output = run! { "zpool list -Ho name,health,cap,alloc,size" }?;
+
+    let mut pools = vec![];
+    for line in output.lines() {
+        let spl: Vec<_> = line.split_whitespace().collect();
+        let name = spl[0].to_string();
+        let scrub_status = scrub_status(&name)?;
+
+        pools.push(Pool {
+            health: spl[1].to_string(),
+            capacity: spl[2].to_string(),
+            alloc: spl[3].to_string(),
+            size: spl[4].to_string(),
+            name,
+            scrub_status,
+        });
+    }
+
+    Ok(pools)
+}
+
+/// Summarize the scrub/resilver state from `zpool status`'s `scan:` line,
+/// including the age of the last completed scrub, e.g. `scrub 3d ago`.
+fn scrub_status(pool: &str) -> Result<String> {
+    let cmd = format!("zpool status {}", pool);
+    let output = run! { cmd }?;
+
+    let scan = output
+        .lines()
+        .map(str::trim)
+        .find(|line| line.starts_with("scan:"))
+        .unwrap_or("scan: none requested");
+
+    if scan.contains("resilver in progress") {
+        return Ok("resilvering".into());
+    }
+    if scan.contains("scrub in progress") {
+        return Ok("scrubbing".into());
+    }
+
+    let verb = if scan.contains("resilvered") {
+        "resilver"
+    } else if scan.contains("scrub repaired") {
+        "scrub"
+    } else {
+        return Ok("no scrub".into());
+    };
+
+    // a completed scan ends with "... on <date>"; render its age in days, or
+    // fall back to a bare "done" if the timestamp can't be parsed
+    let age = scan.split(" on ").nth(1).and_then(scan_age_days);
+    match age {
+        Some(days) => Ok(format!("{} {}d ago", verb, days)),
+        None => Ok(format!("{} done", verb)),
+    }
+}
+
+/// Parse a `zpool status` scan date ("Mon Jan  1 03:00:00 2024") and return
+/// how many days ago it was.
+fn scan_age_days(date: &str) -> Option<u64> {
+    let t: Vec<_> = date.split_whitespace().collect();
+    if t.len() != 5 {
+        return None;
+    }
+
+    let month = month_number(t[1])?;
+    let day: i64 = t[2].parse().ok()?;
+    let hms: Vec<_> = t[3].split(':').collect();
+    let hour: i64 = hms.first()?.parse().ok()?;
+    let min: i64 = hms.get(1)?.parse().ok()?;
+    let sec: i64 = hms.get(2)?.parse().ok()?;
+    let year: i64 = t[4].parse().ok()?;
+
+    let then = days_from_civil(year, month, day) * 86_400
+        + hour * 3_600
+        + min * 60
+        + sec;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+
+    Some(now.saturating_sub(then.max(0) as u64) / 86_400)
+}
+
+/// Map a three-letter month abbreviation to its number (1-12).
+fn month_number(abbrev: &str) -> Option<i64> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct",
+        "Nov", "Dec",
+    ];
+    MONTHS.iter().position(|m| *m == abbrev).map(|i| i as i64 + 1)
+}
+
+/// Days since the Unix epoch for a civil (proleptic Gregorian) date, after
+/// Howard Hinnant's `days_from_civil` algorithm.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let yoe = year - era * 400;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5
+        + day
+        - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Count snapshots and sum their used space across all datasets.
+fn gather_snapshots() -> Result<Snapshots> {
+    let output = run! { "zfs list -Hp -t snapshot -o name,used" }?;
+
+    let mut count = 0;
+    let mut used = 0;
+    for line in output.lines() {
+        let spl: Vec<_> = line.split('\t').collect();
+        used += spl[1].parse::<u64>().unwrap_or(0);
+        count += 1;
+    }
+
+    Ok(Snapshots { count, used })
+}
+
+/// Gather the full ZFS report from the host.
+fn gather() -> Result<Report> {
+    let pools = gather_pools()?;
+    let snapshots = gather_snapshots()?;
+
+    Ok(Report { pools, snapshots })
+}
+
+/**
+ * The ZFS line for the text layout: each pool's health/capacity/scrub state
+ * followed by a snapshot summary.
+ */
+pub fn report() -> Result<String> {
+    Ok(gather()?.summary())
+}
+
+/**
+ * The ZFS report as structured JSON (pools + snapshot aggregation), so
+ * `--json` emits the subsystem as an object rather than a flattened string.
+ */
+pub fn report_json() -> Result<Value> {
+    Ok(serde_json::to_value(gather()?)?)
+}